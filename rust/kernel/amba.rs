@@ -0,0 +1,223 @@
+// SPDX-License-Identifier: GPL-2.0
+
+//! Amba (ARM Primecell) bus devices and drivers.
+//!
+//! C header: [`include/linux/amba/bus.h`](../../../../include/linux/amba/bus.h)
+
+#![allow(dead_code)]
+
+use crate::{
+    bindings, device, driver,
+    error::{from_result, to_result, Result},
+    io_mem::Resource,
+    str::CStr,
+    types::ForeignOwnable,
+    ThisModule,
+};
+
+/// An adapter for the registration of Amba drivers.
+pub struct Adapter<T: Driver>(T);
+
+impl<T: Driver> driver::DriverOps for Adapter<T> {
+    type RegType = bindings::amba_driver;
+
+    unsafe fn register(
+        reg: *mut bindings::amba_driver,
+        name: &'static CStr,
+        module: &'static ThisModule,
+    ) -> Result {
+        let adrv: &mut bindings::amba_driver = unsafe { &mut *reg };
+
+        adrv.drv.name = name.as_char_ptr();
+        adrv.probe = Some(Self::probe_callback);
+        adrv.remove = Some(Self::remove_callback);
+        adrv.id_table = T::ID_TABLE.as_ref();
+        to_result(unsafe { bindings::__amba_driver_register(reg, module.0) })
+    }
+
+    unsafe fn unregister(reg: *mut bindings::amba_driver) {
+        unsafe { bindings::amba_driver_unregister(reg) }
+    }
+}
+
+impl<T: Driver> Adapter<T> {
+    extern "C" fn probe_callback(
+        adev: *mut bindings::amba_device,
+        id: *const bindings::amba_id,
+    ) -> core::ffi::c_int {
+        from_result(|| {
+            let mut dev = unsafe { Device::from_ptr(adev) };
+
+            // SAFETY: `id` is a pointer within the static table, so it's always valid.
+            let offset = unsafe { (*id).data as isize };
+            // SAFETY: The offset comes from a previous call to `offset_from` in `IdArray::new`,
+            // which guarantees that the resulting pointer is within the table.
+            let info = {
+                let ptr = unsafe { id.cast::<u8>().offset(offset).cast::<Option<T::IdInfo>>() };
+                unsafe { (&*ptr).as_ref() }
+            };
+            let data = T::probe(&mut dev, info)?;
+            unsafe { bindings::amba_set_drvdata(adev, data.into_foreign() as _) };
+            Ok(0)
+        })
+    }
+
+    extern "C" fn remove_callback(adev: *mut bindings::amba_device) {
+        let ptr = unsafe { bindings::amba_get_drvdata(adev) };
+        let data = unsafe { T::Data::from_foreign(ptr) };
+        T::remove(&data);
+        <T::Data as driver::DeviceRemoval>::device_remove(&data);
+    }
+}
+
+/// Abstraction for bindings::amba_id.
+#[derive(Clone, Copy)]
+pub struct DeviceId {
+    /// Peripheral ID.
+    pub id: u32,
+    /// Mask that identifies which bits of `id` are significant when matching.
+    pub mask: u32,
+}
+
+impl DeviceId {
+    /// Low-level AMBA_ID macro.
+    pub const fn new(id: u32, mask: u32) -> Self {
+        Self { id, mask }
+    }
+
+    pub const fn to_rawid(&self, offset: isize) -> bindings::amba_id {
+        bindings::amba_id {
+            id: self.id,
+            mask: self.mask,
+            data: offset as _,
+        }
+    }
+}
+
+// SAFETY: `ZERO` is all zeroed-out and `to_rawid` stores `offset` in `amba_id::data`.
+unsafe impl driver::RawDeviceId for DeviceId {
+    type RawType = bindings::amba_id;
+
+    const ZERO: Self::RawType = bindings::amba_id {
+        id: 0,
+        mask: 0,
+        data: core::ptr::null_mut(),
+    };
+}
+
+/// Define a const amba device id table
+///
+/// # Examples
+///
+/// ```ignore
+/// # use kernel::{amba, define_amba_id_table};
+/// #
+/// struct MyDriver;
+/// impl amba::Driver for MyDriver {
+///     // [...]
+/// #   fn probe(_dev: &mut amba::Device, _id_info: Option<&Self::IdInfo>) -> Result {
+/// #       Ok(())
+/// #   }
+/// #   define_amba_id_table! {u32, [
+/// #       (amba::DeviceId::new(0x00041011, 0x000fffff), None),
+/// #   ]}
+/// }
+/// ```
+#[macro_export]
+macro_rules! define_amba_id_table {
+    ($data_type:ty, $($t:tt)*) => {
+        type IdInfo = $data_type;
+        const ID_TABLE: $crate::driver::IdTable<'static, $crate::amba::DeviceId, $data_type> = {
+            $crate::define_id_array!(ARRAY, $crate::amba::DeviceId, $data_type, $($t)* );
+            ARRAY.as_table()
+        };
+    };
+}
+pub use define_amba_id_table;
+
+/// An Amba driver.
+pub trait Driver {
+    /// Data stored on device by driver.
+    ///
+    /// Corresponds to the data set or retrieved via the kernel's
+    /// `amba_{set,get}_drvdata()` functions.
+    ///
+    /// Require that `Data` implements `ForeignOwnable`. We guarantee to
+    /// never move the underlying wrapped data structure.
+    type Data: ForeignOwnable + driver::DeviceRemoval = ();
+
+    /// The type holding information about each device id supported by the driver.
+    type IdInfo: 'static = ();
+
+    /// The table of device ids supported by the driver.
+    const ID_TABLE: driver::IdTable<'static, DeviceId, Self::IdInfo>;
+
+    /// Amba driver probe.
+    ///
+    /// Called when a new amba device is added or discovered.
+    /// Implementers should attempt to initialize the device here.
+    fn probe(dev: &mut Device, id: Option<&Self::IdInfo>) -> Result<Self::Data>;
+
+    /// Amba driver remove.
+    ///
+    /// Called when an amba device is removed.
+    /// Implementers should prepare the device for complete removal here.
+    fn remove(_data: &Self::Data);
+}
+
+/// An Amba (Primecell) device.
+///
+/// # Invariants
+///
+/// The field `ptr` is non-null and valid for the lifetime of the object.
+pub struct Device {
+    ptr: *mut bindings::amba_device,
+}
+
+impl Device {
+    pub unsafe fn from_ptr(ptr: *mut bindings::amba_device) -> Self {
+        Self { ptr }
+    }
+
+    pub unsafe fn as_ptr(&self) -> *mut bindings::amba_device {
+        self.ptr
+    }
+
+    pub fn irq(&self, index: usize) -> Option<u32> {
+        let adev = unsafe { &*self.ptr };
+
+        if index >= adev.irq.len() || adev.irq[index] == 0 {
+            None
+        } else {
+            Some(adev.irq[index])
+        }
+    }
+
+    pub fn resource(&self) -> Option<Resource> {
+        let adev = unsafe { &*self.ptr };
+        Resource::new(adev.res.start, adev.res.end)
+    }
+
+    pub fn revision(&self) -> Option<u32> {
+        let adev = unsafe { &*self.ptr };
+        let periphid = adev.periphid;
+
+        if periphid == 0 {
+            None
+        } else {
+            Some((periphid >> 20) & 0xf)
+        }
+    }
+
+    pub fn periphid(&self) -> u32 {
+        let adev = unsafe { &*self.ptr };
+        adev.periphid as _
+    }
+}
+
+unsafe impl device::RawDevice for Device {
+    fn raw_device(&self) -> *mut bindings::device {
+        // SAFETY: By the type invariants, we know that `self.ptr` is non-null and valid.
+        unsafe { &mut (*self.ptr).dev }
+    }
+}
@@ -56,7 +56,11 @@ pub unsafe trait RawDevice {
         }
     }
 
-    fn dma_map_sg(&self, sglist: &mut [bindings::scatterlist], dir: u32) -> Result {
+    fn dma_map_sg<'a>(
+        &self,
+        sglist: &'a mut [bindings::scatterlist],
+        dir: u32,
+    ) -> Result<DmaMapSg<'a>> {
         let dev = self.raw_device();
         let count = sglist.len().try_into()?;
         let ret = unsafe {
@@ -68,11 +72,17 @@ pub unsafe trait RawDevice {
                 bindings::DMA_ATTR_NO_WARN.into(),
             )
         };
-        // TODO: It may map fewer than what was requested. What happens then?
+        // `dma_map_sg_attrs` may coalesce adjacent segments, so it can map fewer entries than
+        // `sglist.len()`; the mapped count is reported back via `DmaMapSg::count`.
         if ret == 0 {
             return Err(EIO);
         }
-        Ok(())
+        Ok(DmaMapSg {
+            dev: Device::from_dev(self),
+            sglist,
+            dir,
+            mapped_count: ret as u32,
+        })
     }
 
     fn dma_unmap_sg(&self, sglist: &mut [bindings::scatterlist], dir: u32) {
@@ -80,6 +90,126 @@ pub unsafe trait RawDevice {
         let count = sglist.len() as _;
         unsafe { bindings::dma_unmap_sg_attrs(dev, &mut sglist[0], count, dir, 0) };
     }
+
+    /// Allocates a coherent (cache-coherent, contiguous) DMA buffer of `size` bytes.
+    ///
+    /// Returns a [`DmaAllocation`] guard that frees the buffer with `dma_free_coherent` when
+    /// dropped.
+    fn dma_alloc_coherent(&self, size: usize) -> Result<DmaAllocation> {
+        let dev = self.raw_device();
+        let mut dma_handle: bindings::dma_addr_t = 0;
+        // SAFETY: `dev` is valid, per the safety requirements of `RawDevice::raw_device`.
+        let cpu_addr = unsafe {
+            bindings::dma_alloc_attrs(dev, size, &mut dma_handle, bindings::GFP_KERNEL, 0)
+        };
+        if cpu_addr.is_null() {
+            return Err(ENOMEM);
+        }
+
+        // INVARIANT: `cpu_addr` and `dma_handle` were just returned together by
+        // `dma_alloc_attrs`, so they describe the same `size`-byte coherent buffer.
+        Ok(DmaAllocation {
+            dev: Device::from_dev(self),
+            cpu_addr,
+            dma_handle,
+            size,
+        })
+    }
+}
+
+/// A coherent (cache-coherent, contiguous) DMA buffer.
+///
+/// Allocated by [`RawDevice::dma_alloc_coherent`]; the underlying buffer is freed with
+/// `dma_free_coherent` when this value is dropped.
+///
+/// # Invariants
+///
+/// `cpu_addr` and `dma_handle` were returned together by a successful call to
+/// `dma_alloc_attrs` on `dev`, and describe a buffer of `size` bytes that has not yet been
+/// freed.
+pub struct DmaAllocation {
+    dev: Device,
+    cpu_addr: *mut core::ffi::c_void,
+    dma_handle: bindings::dma_addr_t,
+    size: usize,
+}
+
+impl DmaAllocation {
+    /// Returns the bus address to program into the device for DMA.
+    pub fn dma_handle(&self) -> bindings::dma_addr_t {
+        self.dma_handle
+    }
+
+    /// Returns the size, in bytes, of this buffer.
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Returns the CPU mapping of this buffer as a byte slice.
+    pub fn as_slice(&self) -> &[u8] {
+        // SAFETY: `cpu_addr` is valid for `size` bytes for the lifetime of `self`, per the type
+        // invariants.
+        unsafe { core::slice::from_raw_parts(self.cpu_addr.cast(), self.size) }
+    }
+
+    /// Returns the CPU mapping of this buffer as a mutable byte slice.
+    pub fn as_slice_mut(&mut self) -> &mut [u8] {
+        // SAFETY: `cpu_addr` is valid for `size` bytes for the lifetime of `self`, per the type
+        // invariants.
+        unsafe { core::slice::from_raw_parts_mut(self.cpu_addr.cast(), self.size) }
+    }
+}
+
+impl Drop for DmaAllocation {
+    fn drop(&mut self) {
+        // SAFETY: `cpu_addr` and `dma_handle` were returned together by `dma_alloc_attrs` on
+        // `self.dev` and have not been freed yet, per the type invariants.
+        unsafe {
+            bindings::dma_free_attrs(
+                self.dev.raw_device(),
+                self.size,
+                self.cpu_addr,
+                self.dma_handle,
+                0,
+            )
+        };
+    }
+}
+
+/// A DMA-mapped scatter-gather list.
+///
+/// Returned by [`RawDevice::dma_map_sg`]; the list is unmapped with `dma_unmap_sg_attrs` when
+/// this value is dropped.
+///
+/// # Invariants
+///
+/// `sglist` was mapped by a successful call to `dma_map_sg_attrs` on `dev` using direction
+/// `dir`, and has not been unmapped yet.
+pub struct DmaMapSg<'a> {
+    dev: Device,
+    sglist: &'a mut [bindings::scatterlist],
+    dir: u32,
+    mapped_count: u32,
+}
+
+impl DmaMapSg<'_> {
+    /// Returns the number of segments that were actually mapped.
+    ///
+    /// `dma_map_sg_attrs` is allowed to coalesce adjacent segments, so this may be fewer than
+    /// the number of entries originally passed to [`RawDevice::dma_map_sg`].
+    pub fn count(&self) -> u32 {
+        self.mapped_count
+    }
+}
+
+impl Drop for DmaMapSg<'_> {
+    fn drop(&mut self) {
+        let dev = self.dev.raw_device();
+        let count = self.sglist.len() as _;
+        // SAFETY: `self.sglist` was mapped by a prior call to `dma_map_sg_attrs` on `dev` and
+        // has not been unmapped yet, per the type invariants.
+        unsafe { bindings::dma_unmap_sg_attrs(dev, &mut self.sglist[0], count, self.dir, 0) };
+    }
 }
 
 /// A ref-counted device.
@@ -0,0 +1,227 @@
+// SPDX-License-Identifier: GPL-2.0
+
+//! DRM device and driver abstractions.
+//!
+//! C header: [`include/drm/drm_drv.h`](../../../../include/drm/drm_drv.h)
+
+#![allow(dead_code)]
+
+use crate::{
+    bindings, device,
+    error::{from_err_ptr, from_result, to_result},
+    pci,
+    prelude::*,
+    str::CStr,
+    types::ForeignOwnable,
+};
+use core::marker::PhantomData;
+
+/// Flags describing the features a [`Driver`] supports, mirroring the C `DRIVER_*` constants.
+pub mod features {
+    use crate::bindings;
+
+    /// Driver supports mode setting interfaces (KMS).
+    pub const MODESET: u32 = bindings::DRIVER_MODESET;
+    /// Driver supports dedicated render nodes.
+    pub const RENDER: u32 = bindings::DRIVER_RENDER;
+    /// Driver supports the GEM object model.
+    pub const GEM: u32 = bindings::DRIVER_GEM;
+}
+
+/// A DRM driver.
+///
+/// Implementers describe the driver's identity and capabilities through associated
+/// constants, and hook into file lifetime events through the methods below.
+pub trait Driver {
+    /// Data associated with the DRM device, stored on [`Device`] for the lifetime of the
+    /// registration.
+    type Data: ForeignOwnable + Sync + Send = ();
+
+    /// Feature flags (a bitwise-or of [`features`] constants).
+    const DRIVER_FEATURES: u32;
+
+    /// Driver name, as shown to userspace (e.g. via `drm_version`).
+    const NAME: &'static CStr;
+
+    /// Human-readable driver description.
+    const DESC: &'static CStr;
+
+    /// Driver date, in `YYYYMMDD` form.
+    const DATE: &'static CStr;
+
+    /// Called when a userspace process opens the DRM device.
+    fn open(_data: &Self::Data, _file: &File) -> Result {
+        Ok(())
+    }
+
+    /// Called when the last reference to an open file is dropped.
+    fn postclose(_data: &Self::Data, _file: &File) {}
+}
+
+/// A handle to an open DRM file (`struct drm_file`).
+///
+/// # Invariants
+///
+/// The field `ptr` is non-null and valid for the duration of the callback that hands out the
+/// reference to this type.
+pub struct File {
+    ptr: *mut bindings::drm_file,
+}
+
+impl File {
+    /// Creates a `File` from a raw `drm_file` pointer.
+    ///
+    /// # Safety
+    ///
+    /// Callers must ensure that `ptr` is valid for the lifetime of the returned [`File`].
+    unsafe fn from_ptr(ptr: *mut bindings::drm_file) -> Self {
+        Self { ptr }
+    }
+}
+
+/// A DRM device.
+///
+/// Wraps a `struct drm_device` allocated with `drm_dev_alloc`, holding the driver's private
+/// `Data` for the lifetime of the registration.
+///
+/// # Invariants
+///
+/// `ptr` is valid, non-null, and was allocated by `drm_dev_alloc`.
+pub struct Device<T: Driver> {
+    ptr: *mut bindings::drm_device,
+    _p: PhantomData<T>,
+}
+
+impl<T: Driver> Device<T> {
+    /// Creates a `Device` from a raw `drm_device` pointer, without taking ownership of it.
+    ///
+    /// # Safety
+    ///
+    /// Callers must ensure that `ptr` is valid for the lifetime of the returned [`Device`].
+    unsafe fn from_ptr(ptr: *mut bindings::drm_device) -> Self {
+        Self {
+            ptr,
+            _p: PhantomData,
+        }
+    }
+}
+
+unsafe impl<T: Driver> device::RawDevice for Device<T> {
+    fn raw_device(&self) -> *mut bindings::device {
+        // SAFETY: By the type invariants, we know that `self.ptr` is non-null and valid, and
+        // `drm_device::dev` points at the parent `struct device`.
+        unsafe { (*self.ptr).dev }
+    }
+}
+
+/// Driver registration primitives.
+pub mod drv {
+    use super::*;
+
+    extern "C" fn open_callback<T: Driver>(
+        raw_dev: *mut bindings::drm_device,
+        raw_file: *mut bindings::drm_file,
+    ) -> core::ffi::c_int {
+        from_result(|| {
+            let data = unsafe { T::Data::borrow((*raw_dev).dev_private) };
+            T::open(data, &unsafe { File::from_ptr(raw_file) })?;
+            Ok(0)
+        })
+    }
+
+    extern "C" fn postclose_callback<T: Driver>(
+        raw_dev: *mut bindings::drm_device,
+        raw_file: *mut bindings::drm_file,
+    ) {
+        let data = unsafe { T::Data::borrow((*raw_dev).dev_private) };
+        T::postclose(data, &unsafe { File::from_ptr(raw_file) });
+    }
+
+    /// A registration of a [`Driver`], tied to the lifetime of a parent [`pci::Device`].
+    ///
+    /// Allocates a `struct drm_device` on construction; [`Registration::register`] makes it
+    /// visible to userspace. On drop (RAII), the device is unregistered if it was registered and
+    /// then freed, mirroring the [`pci::Adapter`]/[`device::Device`] ref-counting pattern
+    /// used elsewhere.
+    pub struct Registration<T: Driver> {
+        drm: Device<T>,
+        registered: bool,
+    }
+
+    impl<T: Driver> Registration<T> {
+        /// Allocates a new `drm_device` for `parent` and stores `data` as its private data.
+        pub fn new(parent: &pci::Device, data: T::Data) -> Result<Self> {
+            // SAFETY: `parent.raw_device()` is valid for the duration of this call, per the
+            // `device::RawDevice` safety requirements.
+            //
+            // `drm_dev_alloc` returns an `ERR_PTR()`-encoded pointer on failure rather than
+            // `NULL`, so the result must go through `from_err_ptr` rather than a null check.
+            let ptr = from_err_ptr(unsafe {
+                bindings::drm_dev_alloc(&Self::DRIVER_OPS, device::RawDevice::raw_device(parent))
+            })?;
+
+            // SAFETY: `ptr` was just allocated by `drm_dev_alloc`.
+            unsafe { (*ptr).dev_private = data.into_foreign() as _ };
+
+            Ok(Self {
+                // SAFETY: `ptr` is valid, having just been allocated above.
+                drm: unsafe { Device::from_ptr(ptr) },
+                registered: false,
+            })
+        }
+
+        // The `struct file_operations` backing the device node the DRM core creates for this
+        // driver. `drm_open`/`drm_release` are the standard helpers that set up and tear down a
+        // `struct drm_file` around our own `open`/`postclose` callbacks.
+        const FILE_OPS: bindings::file_operations = {
+            // SAFETY: `file_operations` is a plain-old-data C struct; the all-zero bit pattern
+            // (i.e. every optional callback/field absent) is a valid value.
+            let mut fops: bindings::file_operations = unsafe { core::mem::zeroed() };
+            fops.open = Some(bindings::drm_open);
+            fops.release = Some(bindings::drm_release);
+            fops
+        };
+
+        const DRIVER_OPS: bindings::drm_driver = {
+            // SAFETY: `drm_driver` is a plain-old-data C struct; the all-zero bit pattern
+            // (i.e. every optional callback/field absent) is a valid value.
+            let mut ops: bindings::drm_driver = unsafe { core::mem::zeroed() };
+            ops.driver_features = T::DRIVER_FEATURES;
+            ops.open = Some(open_callback::<T>);
+            ops.postclose = Some(postclose_callback::<T>);
+            ops.name = T::NAME.as_char_ptr();
+            ops.desc = T::DESC.as_char_ptr();
+            ops.date = T::DATE.as_char_ptr();
+            ops.fops = &Self::FILE_OPS;
+            ops
+        };
+
+        /// Registers the device with the DRM subsystem, making it visible to userspace.
+        pub fn register(&mut self) -> Result {
+            to_result(unsafe { bindings::drm_dev_register(self.drm.ptr, 0) })?;
+            self.registered = true;
+            Ok(())
+        }
+    }
+
+    impl<T: Driver> Drop for Registration<T> {
+        fn drop(&mut self) {
+            if self.registered {
+                // SAFETY: `self.drm.ptr` is valid and was previously registered, per the
+                // `registered` check above.
+                unsafe { bindings::drm_dev_unregister(self.drm.ptr) };
+            }
+
+            // SAFETY: `self.drm.ptr` is valid, and its `dev_private` was initialised with the
+            // pointer returned by `data.into_foreign()` in `new`. The device is unregistered by
+            // now (or was never registered), so nothing else can still be using it.
+            let foreign = unsafe { (*self.drm.ptr).dev_private };
+            // SAFETY: `foreign` is the same pointer `into_foreign()` returned in `new`, and has
+            // not been reconstructed before.
+            drop(unsafe { T::Data::from_foreign(foreign) });
+
+            // SAFETY: `self.drm.ptr` was allocated by `drm_dev_alloc` in `new`.
+            unsafe { bindings::drm_dev_put(self.drm.ptr) };
+        }
+    }
+}
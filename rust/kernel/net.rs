@@ -0,0 +1,250 @@
+// SPDX-License-Identifier: GPL-2.0
+
+//! Networking devices.
+//!
+//! C header: [`include/linux/netdevice.h`](../../../../include/linux/netdevice.h)
+
+#![allow(dead_code)]
+
+use crate::{
+    bindings,
+    error::{from_result, to_result},
+    prelude::*,
+    str::CStr,
+    types::ForeignOwnable,
+};
+use core::marker::PhantomData;
+
+/// Operations implemented by a network device driver.
+///
+/// Each method corresponds to a field of the C `struct net_device_ops`. Every one of these fields
+/// is always set; methods a driver does not override run their default implementation, which
+/// does nothing (`start_xmit` frees the packet and reports it as sent), so drivers only need to
+/// override what they actually implement.
+pub trait DeviceOperations {
+    /// Data stored in the netdev private area by the driver.
+    type Data: ForeignOwnable = ();
+
+    /// Called when the network device transitions to the up state.
+    fn open(_data: &Self::Data, _dev: &Device) -> Result {
+        Ok(())
+    }
+
+    /// Called when the network device transitions to the down state.
+    fn stop(_data: &Self::Data, _dev: &Device) -> Result {
+        Ok(())
+    }
+
+    /// Called to transmit a packet. Implementers take ownership of `skb`.
+    fn start_xmit(_data: &Self::Data, _dev: &Device, skb: *mut bindings::sk_buff) -> u32 {
+        // NETDEV_TX_OK: the driver consumed (or dropped) the packet.
+        unsafe { bindings::dev_kfree_skb(skb) };
+        bindings::netdev_tx_t_NETDEV_TX_OK
+    }
+
+    /// Called to update the device's receive-mode (promiscuous/multicast) filters.
+    fn set_rx_mode(_data: &Self::Data, _dev: &Device) {}
+
+    /// Called to fetch the device's 64-bit statistics.
+    fn get_stats64(_data: &Self::Data, _dev: &Device, _storage: *mut bindings::rtnl_link_stats64) {}
+}
+
+/// A registration of a `struct net_device_ops` table bound to a [`DeviceOperations`]
+/// implementation.
+struct OperationsVtable<T: DeviceOperations>(PhantomData<T>);
+
+impl<T: DeviceOperations> OperationsVtable<T> {
+    /// Reads back the foreign pointer that [`Registration::try_new`] stored in the netdev
+    /// private area, and borrows the `T::Data` it represents.
+    ///
+    /// # Safety
+    ///
+    /// `dev` must point at a `net_device` whose private area was initialised by
+    /// [`Registration::try_new`] and has not yet been torn down.
+    unsafe fn data<'a>(
+        dev: *mut bindings::net_device,
+    ) -> <T::Data as ForeignOwnable>::Borrowed<'a> {
+        // SAFETY: The private area was initialised by `try_new` to hold exactly the pointer
+        // returned by `data.into_foreign()`, per the safety requirements of this function.
+        let ptr = unsafe { *(bindings::netdev_priv(dev) as *const *const core::ffi::c_void) };
+        // SAFETY: `ptr` is the same pointer `into_foreign()` returned in `try_new`, and the
+        // device (and therefore the netdev private area) outlives this borrow.
+        unsafe { T::Data::borrow(ptr) }
+    }
+
+    extern "C" fn open_callback(dev: *mut bindings::net_device) -> core::ffi::c_int {
+        from_result(|| {
+            // SAFETY: `dev` is a live `net_device` handed to us by the networking core.
+            let data = unsafe { Self::data(dev) };
+            T::open(data, &unsafe { Device::from_ptr(dev) })?;
+            Ok(0)
+        })
+    }
+
+    extern "C" fn stop_callback(dev: *mut bindings::net_device) -> core::ffi::c_int {
+        from_result(|| {
+            // SAFETY: `dev` is a live `net_device` handed to us by the networking core.
+            let data = unsafe { Self::data(dev) };
+            T::stop(data, &unsafe { Device::from_ptr(dev) })?;
+            Ok(0)
+        })
+    }
+
+    extern "C" fn start_xmit_callback(
+        skb: *mut bindings::sk_buff,
+        dev: *mut bindings::net_device,
+    ) -> bindings::netdev_tx_t {
+        // SAFETY: `dev` is a live `net_device` handed to us by the networking core.
+        let data = unsafe { Self::data(dev) };
+        T::start_xmit(data, &unsafe { Device::from_ptr(dev) }, skb)
+    }
+
+    extern "C" fn set_rx_mode_callback(dev: *mut bindings::net_device) {
+        // SAFETY: `dev` is a live `net_device` handed to us by the networking core.
+        let data = unsafe { Self::data(dev) };
+        T::set_rx_mode(data, &unsafe { Device::from_ptr(dev) });
+    }
+
+    extern "C" fn get_stats64_callback(
+        dev: *mut bindings::net_device,
+        storage: *mut bindings::rtnl_link_stats64,
+    ) {
+        // SAFETY: `dev` is a live `net_device` handed to us by the networking core.
+        let data = unsafe { Self::data(dev) };
+        T::get_stats64(data, &unsafe { Device::from_ptr(dev) }, storage);
+    }
+
+    // SAFETY: `net_device_ops` is a plain-old-data C struct made up entirely of optional
+    // function pointers, for which the all-zero bit pattern is valid.
+    const VTABLE: bindings::net_device_ops = {
+        let mut ops: bindings::net_device_ops = unsafe { core::mem::zeroed() };
+        ops.ndo_open = Some(Self::open_callback);
+        ops.ndo_stop = Some(Self::stop_callback);
+        ops.ndo_start_xmit = Some(Self::start_xmit_callback);
+        ops.ndo_set_rx_mode = Some(Self::set_rx_mode_callback);
+        ops.ndo_get_stats64 = Some(Self::get_stats64_callback);
+        ops
+    };
+}
+
+/// A network device.
+///
+/// # Invariants
+///
+/// The field `ptr` is non-null and valid for the lifetime of the object.
+pub struct Device {
+    ptr: *mut bindings::net_device,
+}
+
+impl Device {
+    /// Creates a `Device` from a raw `net_device` pointer, without taking ownership of it.
+    ///
+    /// # Safety
+    ///
+    /// Callers must ensure that `ptr` is valid for the lifetime of the returned [`Device`].
+    unsafe fn from_ptr(ptr: *mut bindings::net_device) -> Self {
+        Self { ptr }
+    }
+}
+
+/// A registration of a network device.
+///
+/// This is an RAII wrapper around a `struct net_device` allocated by [`Registration::try_new`].
+/// The device is unregistered and freed when the [`Registration`] is dropped.
+///
+/// # Invariants
+///
+/// `ptr` is valid and was allocated by `alloc_netdev_mqs`.
+pub struct Registration<T: DeviceOperations> {
+    ptr: *mut bindings::net_device,
+    registered: bool,
+    _p: PhantomData<T>,
+}
+
+// SAFETY: `Registration` only holds a pointer to a C `net_device`, which is safe to be used
+// from any thread.
+unsafe impl<T: DeviceOperations> Send for Registration<T> {}
+
+// SAFETY: `&Registration` does not give access to any fields that aren't safe to access
+// concurrently.
+unsafe impl<T: DeviceOperations> Sync for Registration<T> {}
+
+impl<T: DeviceOperations> Registration<T> {
+    /// Allocates a new ethernet `net_device`, stashes `data` in its private area as a
+    /// [`ForeignOwnable`], and sets up its `net_device_ops` table.
+    pub fn try_new(name: &CStr, data: T::Data) -> Result<Self> {
+        // The private area only needs to hold the (pointer-sized) foreign handle returned by
+        // `data.into_foreign()`, not `T::Data` itself.
+        // SAFETY: `alloc_etherdev_mqs` returns either a valid pointer or `NULL`.
+        let ptr = unsafe {
+            bindings::alloc_etherdev_mqs(
+                core::mem::size_of::<*const core::ffi::c_void>() as _,
+                1,
+                1,
+            )
+        };
+        if ptr.is_null() {
+            return Err(ENOMEM);
+        }
+
+        // SAFETY: `ptr` was just allocated and is therefore valid.
+        let dev = unsafe { &mut *ptr };
+        dev.netdev_ops = &OperationsVtable::<T>::VTABLE;
+
+        // `name` conventionally contains a `%d` placeholder (e.g. `"eth%d"`) for the unit
+        // number the networking core assigns, so it must be copied verbatim rather than passed
+        // through a printf-style formatter.
+        // SAFETY: `name` is a valid, NUL-terminated C string, and `dev.name` has enough room
+        // for `IFNAMSIZ` bytes, as guaranteed by `alloc_etherdev_mqs`.
+        unsafe {
+            bindings::strscpy(dev.name.as_mut_ptr(), name.as_char_ptr(), dev.name.len());
+        }
+
+        // SAFETY: `netdev_priv` returns a pointer to the private area, which is valid for a
+        // write of one pointer, as requested above.
+        unsafe {
+            core::ptr::write(
+                bindings::netdev_priv(ptr) as *mut *const core::ffi::c_void,
+                data.into_foreign(),
+            );
+        }
+
+        Ok(Self {
+            ptr,
+            registered: false,
+            _p: PhantomData,
+        })
+    }
+
+    /// Registers the device with the networking stack, making it visible to userspace.
+    pub fn register(&mut self) -> Result {
+        if self.registered {
+            return Err(EINVAL);
+        }
+        to_result(unsafe { bindings::register_netdev(self.ptr) })?;
+        self.registered = true;
+        Ok(())
+    }
+}
+
+impl<T: DeviceOperations> Drop for Registration<T> {
+    fn drop(&mut self) {
+        if self.registered {
+            // SAFETY: `self.ptr` is valid and was previously registered, per the type
+            // invariants and the `registered` check above.
+            unsafe { bindings::unregister_netdev(self.ptr) };
+        }
+
+        // SAFETY: `self.ptr`'s private area was initialised with the pointer returned by
+        // `data.into_foreign()` in `try_new`, and nothing else can access it once the device is
+        // unregistered.
+        let foreign =
+            unsafe { *(bindings::netdev_priv(self.ptr) as *const *const core::ffi::c_void) };
+        // SAFETY: `foreign` is the same pointer `into_foreign()` returned in `try_new`, and has
+        // not been reconstructed before.
+        drop(unsafe { T::Data::from_foreign(foreign) });
+
+        // SAFETY: `self.ptr` was allocated by `alloc_etherdev_mqs` in `try_new`.
+        unsafe { bindings::free_netdev(self.ptr) };
+    }
+}
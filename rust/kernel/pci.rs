@@ -11,6 +11,7 @@ use crate::{
     error::{from_result, to_result, Error, Result},
     io_mem::Resource,
     irq,
+    power::PmMessage,
     str::CStr,
     types::ForeignOwnable,
     ThisModule,
@@ -34,6 +35,7 @@ impl<T: Driver> driver::DriverOps for Adapter<T> {
         pdrv.probe = Some(Self::probe_callback);
         pdrv.remove = Some(Self::remove_callback);
         pdrv.id_table = T::ID_TABLE.as_ref();
+        pdrv.driver.pm = &Self::PM_OPS as *const _;
         to_result(unsafe { bindings::__pci_register_driver(reg, module.0, name.as_char_ptr()) })
     }
 
@@ -43,6 +45,107 @@ impl<T: Driver> driver::DriverOps for Adapter<T> {
 }
 
 impl<T: Driver> Adapter<T> {
+    // SAFETY: `dev_pm_ops` is a plain-old-data C struct made up entirely of optional function
+    // pointers, for which the all-zero bit pattern (i.e. every callback absent) is valid.
+    const PM_OPS: bindings::dev_pm_ops = {
+        let mut ops: bindings::dev_pm_ops = unsafe { core::mem::zeroed() };
+        ops.suspend = Some(Self::suspend_callback);
+        ops.resume = Some(Self::resume_callback);
+        ops.freeze = Some(Self::freeze_callback);
+        ops.restore = Some(Self::restore_callback);
+        ops.runtime_suspend = Some(Self::runtime_suspend_callback);
+        ops.runtime_resume = Some(Self::runtime_resume_callback);
+        ops.runtime_idle = Some(Self::runtime_idle_callback);
+        ops
+    };
+
+    extern "C" fn suspend_callback(
+        dev: *mut bindings::device,
+        msg: bindings::pm_message_t,
+    ) -> core::ffi::c_int {
+        from_result(|| {
+            let pdev = unsafe { bindings::to_pci_dev(dev) };
+            let ptr = unsafe { bindings::pci_get_drvdata(pdev) };
+            // SAFETY: `ptr` was returned by a previous call to `into_foreign` in `probe_callback`
+            // and is still owned by the device, so it is safe to borrow it here.
+            let data = unsafe { T::Data::borrow(ptr) };
+            T::suspend(data, PmMessage::from_raw(msg))?;
+            Ok(0)
+        })
+    }
+
+    extern "C" fn resume_callback(dev: *mut bindings::device) -> core::ffi::c_int {
+        from_result(|| {
+            let pdev = unsafe { bindings::to_pci_dev(dev) };
+            let ptr = unsafe { bindings::pci_get_drvdata(pdev) };
+            // SAFETY: `ptr` was returned by a previous call to `into_foreign` in `probe_callback`
+            // and is still owned by the device, so it is safe to borrow it here.
+            let data = unsafe { T::Data::borrow(ptr) };
+            T::resume(data)?;
+            Ok(0)
+        })
+    }
+
+    extern "C" fn freeze_callback(dev: *mut bindings::device) -> core::ffi::c_int {
+        from_result(|| {
+            let pdev = unsafe { bindings::to_pci_dev(dev) };
+            let ptr = unsafe { bindings::pci_get_drvdata(pdev) };
+            // SAFETY: `ptr` was returned by a previous call to `into_foreign` in `probe_callback`
+            // and is still owned by the device, so it is safe to borrow it here.
+            let data = unsafe { T::Data::borrow(ptr) };
+            T::freeze(data)?;
+            Ok(0)
+        })
+    }
+
+    extern "C" fn restore_callback(dev: *mut bindings::device) -> core::ffi::c_int {
+        from_result(|| {
+            let pdev = unsafe { bindings::to_pci_dev(dev) };
+            let ptr = unsafe { bindings::pci_get_drvdata(pdev) };
+            // SAFETY: `ptr` was returned by a previous call to `into_foreign` in `probe_callback`
+            // and is still owned by the device, so it is safe to borrow it here.
+            let data = unsafe { T::Data::borrow(ptr) };
+            T::restore(data)?;
+            Ok(0)
+        })
+    }
+
+    extern "C" fn runtime_suspend_callback(dev: *mut bindings::device) -> core::ffi::c_int {
+        from_result(|| {
+            let pdev = unsafe { bindings::to_pci_dev(dev) };
+            let ptr = unsafe { bindings::pci_get_drvdata(pdev) };
+            // SAFETY: `ptr` was returned by a previous call to `into_foreign` in `probe_callback`
+            // and is still owned by the device, so it is safe to borrow it here.
+            let data = unsafe { T::Data::borrow(ptr) };
+            T::runtime_suspend(data)?;
+            Ok(0)
+        })
+    }
+
+    extern "C" fn runtime_resume_callback(dev: *mut bindings::device) -> core::ffi::c_int {
+        from_result(|| {
+            let pdev = unsafe { bindings::to_pci_dev(dev) };
+            let ptr = unsafe { bindings::pci_get_drvdata(pdev) };
+            // SAFETY: `ptr` was returned by a previous call to `into_foreign` in `probe_callback`
+            // and is still owned by the device, so it is safe to borrow it here.
+            let data = unsafe { T::Data::borrow(ptr) };
+            T::runtime_resume(data)?;
+            Ok(0)
+        })
+    }
+
+    extern "C" fn runtime_idle_callback(dev: *mut bindings::device) -> core::ffi::c_int {
+        from_result(|| {
+            let pdev = unsafe { bindings::to_pci_dev(dev) };
+            let ptr = unsafe { bindings::pci_get_drvdata(pdev) };
+            // SAFETY: `ptr` was returned by a previous call to `into_foreign` in `probe_callback`
+            // and is still owned by the device, so it is safe to borrow it here.
+            let data = unsafe { T::Data::borrow(ptr) };
+            T::runtime_idle(data)?;
+            Ok(0)
+        })
+    }
+
     extern "C" fn probe_callback(
         pdev: *mut bindings::pci_dev,
         id: *const bindings::pci_device_id,
@@ -109,13 +212,24 @@ impl DeviceId {
     }
 
     /// PCI_DEVICE_CLASS macro.
-    pub const fn with_class(class: u32, class_mask: u32) -> Self {
+    ///
+    /// Accepts a typed [`Class`] instead of a bare code, and derives the matching mask
+    /// automatically: the programming interface is only matched when `class` carries one via
+    /// [`Class::Other`]; for the other variants, the programming interface they carry is encoded
+    /// into `class` (so [`Class::to_code`] round-trips) but masked out of matching, leaving only
+    /// the base class and subclass significant.
+    pub const fn with_class(class: Class) -> Self {
+        let class_mask = match class {
+            Class::Other(..) => 0x00ff_ffff,
+            _ => 0x00ff_ff00,
+        };
+
         Self {
             vendor: DeviceId::PCI_ANY_ID,
             device: DeviceId::PCI_ANY_ID,
             subvendor: DeviceId::PCI_ANY_ID,
             subdevice: DeviceId::PCI_ANY_ID,
-            class,
+            class: class.to_code(),
             class_mask,
         }
     }
@@ -148,7 +262,224 @@ unsafe impl driver::RawDeviceId for DeviceId {
         driver_data: 0,
         override_only: 0,
     };
+}
+
+/// A decoded 24-bit PCI class code.
+///
+/// Splits the raw `class` field of a [`DeviceId`] or [`bindings::pci_dev`] into its base class
+/// (bits 16-23), subclass (bits 8-15), and programming interface (bits 0-7). The programming
+/// interface is carried alongside the decoded subclass for every variant, so that
+/// `Class::from_code(code).to_code() == code` always holds.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Class {
+    /// Mass storage controller (base class `0x01`).
+    MassStorage(StorageSubclass, u8),
+    /// Network controller (base class `0x02`).
+    Network(NetworkSubclass, u8),
+    /// Display controller (base class `0x03`).
+    Display(DisplaySubclass, u8),
+    /// Any other, or unrecognised, `(base, subclass, prog-if)` triplet.
+    Other(u8, u8, u8),
+}
+
+impl Class {
+    /// Encodes this class back into the raw 24-bit code stored in `pci_dev::class`.
+    pub const fn to_code(self) -> u32 {
+        let (base, subclass, prog_if) = match self {
+            Self::MassStorage(s, prog_if) => (0x01, s.to_code(), prog_if),
+            Self::Network(s, prog_if) => (0x02, s.to_code(), prog_if),
+            Self::Display(s, prog_if) => (0x03, s.to_code(), prog_if),
+            Self::Other(base, subclass, prog_if) => (base, subclass, prog_if),
+        };
+        ((base as u32) << 16) | ((subclass as u32) << 8) | prog_if as u32
+    }
+
+    /// Decodes a raw 24-bit class code, as read from `pci_dev::class`.
+    pub const fn from_code(code: u32) -> Self {
+        let base = ((code >> 16) & 0xff) as u8;
+        let subclass = ((code >> 8) & 0xff) as u8;
+        let prog_if = (code & 0xff) as u8;
+        match base {
+            0x01 => Self::MassStorage(StorageSubclass::from_code(subclass), prog_if),
+            0x02 => Self::Network(NetworkSubclass::from_code(subclass), prog_if),
+            0x03 => Self::Display(DisplaySubclass::from_code(subclass), prog_if),
+            _ => Self::Other(base, subclass, prog_if),
+        }
+    }
+}
+
+/// Subclasses of [`Class::MassStorage`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StorageSubclass {
+    Scsi,
+    Ide,
+    FloppyDisk,
+    RaidBus,
+    Ata,
+    Sata,
+    Sas,
+    Nvm,
+    Other(u8),
+}
+
+impl StorageSubclass {
+    const fn to_code(self) -> u8 {
+        match self {
+            Self::Scsi => 0x00,
+            Self::Ide => 0x01,
+            Self::FloppyDisk => 0x02,
+            Self::RaidBus => 0x04,
+            Self::Ata => 0x05,
+            Self::Sata => 0x06,
+            Self::Sas => 0x07,
+            Self::Nvm => 0x08,
+            Self::Other(code) => code,
+        }
+    }
+
+    const fn from_code(code: u8) -> Self {
+        match code {
+            0x00 => Self::Scsi,
+            0x01 => Self::Ide,
+            0x02 => Self::FloppyDisk,
+            0x04 => Self::RaidBus,
+            0x05 => Self::Ata,
+            0x06 => Self::Sata,
+            0x07 => Self::Sas,
+            0x08 => Self::Nvm,
+            other => Self::Other(other),
+        }
+    }
+}
+
+/// Subclasses of [`Class::Network`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum NetworkSubclass {
+    Ethernet,
+    TokenRing,
+    Fddi,
+    Atm,
+    Isdn,
+    Other(u8),
+}
+
+impl NetworkSubclass {
+    const fn to_code(self) -> u8 {
+        match self {
+            Self::Ethernet => 0x00,
+            Self::TokenRing => 0x01,
+            Self::Fddi => 0x02,
+            Self::Atm => 0x03,
+            Self::Isdn => 0x04,
+            Self::Other(code) => code,
+        }
+    }
+
+    const fn from_code(code: u8) -> Self {
+        match code {
+            0x00 => Self::Ethernet,
+            0x01 => Self::TokenRing,
+            0x02 => Self::Fddi,
+            0x03 => Self::Atm,
+            0x04 => Self::Isdn,
+            other => Self::Other(other),
+        }
+    }
+}
 
+/// Subclasses of [`Class::Display`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DisplaySubclass {
+    Vga,
+    Xga,
+    ThreeD,
+    Other(u8),
+}
+
+impl DisplaySubclass {
+    const fn to_code(self) -> u8 {
+        match self {
+            Self::Vga => 0x00,
+            Self::Xga => 0x01,
+            Self::ThreeD => 0x02,
+            Self::Other(code) => code,
+        }
+    }
+
+    const fn from_code(code: u8) -> Self {
+        match code {
+            0x00 => Self::Vga,
+            0x01 => Self::Xga,
+            0x02 => Self::ThreeD,
+            other => Self::Other(other),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn class_round_trip() {
+        assert_eq!(
+            Class::from_code(Class::MassStorage(StorageSubclass::Nvm, 0x02).to_code()),
+            Class::MassStorage(StorageSubclass::Nvm, 0x02)
+        );
+        assert_eq!(
+            Class::from_code(Class::Network(NetworkSubclass::Ethernet, 0x00).to_code()),
+            Class::Network(NetworkSubclass::Ethernet, 0x00)
+        );
+        assert_eq!(
+            Class::from_code(Class::Display(DisplaySubclass::Vga, 0x01).to_code()),
+            Class::Display(DisplaySubclass::Vga, 0x01)
+        );
+        assert_eq!(
+            Class::from_code(Class::Other(0x0c, 0x03, 0x30).to_code()),
+            Class::Other(0x0c, 0x03, 0x30)
+        );
+    }
+
+    #[test]
+    fn unrecognised_subclass_round_trips_as_other() {
+        assert_eq!(
+            Class::from_code(Class::MassStorage(StorageSubclass::Other(0xf0), 0x00).to_code()),
+            Class::MassStorage(StorageSubclass::Other(0xf0), 0x00)
+        );
+    }
+}
+
+/// A PCI vendor ID.
+///
+/// Wraps the raw numeric ID and, for a small table of well-known vendors, resolves it to a
+/// human-readable name via [`VendorId::name`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct VendorId(pub u32);
+
+impl VendorId {
+    /// Returns the human-readable name of this vendor, if it is in the built-in table.
+    pub const fn name(self) -> Option<&'static str> {
+        match self.0 {
+            0x8086 => Some("Intel Corporation"),
+            0x1022 => Some("Advanced Micro Devices, Inc. [AMD]"),
+            0x1002 => Some("Advanced Micro Devices, Inc. [AMD/ATI]"),
+            0x10de => Some("NVIDIA Corporation"),
+            0x14e4 => Some("Broadcom Inc."),
+            0x10ec => Some("Realtek Semiconductor Co., Ltd."),
+            0x15b3 => Some("Mellanox Technologies"),
+            0x1af4 => Some("Red Hat, Inc. (QEMU virtio)"),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for VendorId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.name() {
+            Some(name) => write!(f, "{:#06x} ({})", self.0, name),
+            None => write!(f, "{:#06x}", self.0),
+        }
+    }
 }
 
 /// Define a const pci device id table
@@ -166,7 +497,7 @@ unsafe impl driver::RawDeviceId for DeviceId {
 /// #   }
 /// #   define_pci_id_table! {u32, [
 /// #       (pci::DeviceId::new(0x010800, 0xffffff), None),
-/// #       (pci::DeviceId::with_class(0x010802, 0xfffff), Some(0x10)),
+/// #       (pci::DeviceId::with_class(pci::Class::MassStorage(pci::StorageSubclass::Nvm, 0x02)), Some(0x10)),
 /// #   ]}
 /// }
 /// ```
@@ -212,6 +543,57 @@ pub trait Driver {
     /// Called when a platform device is removed.
     /// Implementers should prepare the device for complete removal here.
     fn remove(_data: &Self::Data);
+
+    /// PCI driver suspend.
+    ///
+    /// Called when the device is about to be suspended as part of a system sleep transition.
+    /// `msg` carries the reason for the transition (see [`PmMessage`]).
+    fn suspend(_data: &Self::Data, _msg: PmMessage) -> Result {
+        Ok(())
+    }
+
+    /// PCI driver resume.
+    ///
+    /// Called when the device is resumed as part of a system sleep transition.
+    fn resume(_data: &Self::Data) -> Result {
+        Ok(())
+    }
+
+    /// PCI driver freeze.
+    ///
+    /// Called before a hibernation image is created.
+    fn freeze(_data: &Self::Data) -> Result {
+        Ok(())
+    }
+
+    /// PCI driver restore.
+    ///
+    /// Called after a hibernation image has been restored.
+    fn restore(_data: &Self::Data) -> Result {
+        Ok(())
+    }
+
+    /// PCI driver runtime suspend.
+    ///
+    /// Called when the device is idle and runtime power management decides to suspend it.
+    fn runtime_suspend(_data: &Self::Data) -> Result {
+        Ok(())
+    }
+
+    /// PCI driver runtime resume.
+    ///
+    /// Called to bring a runtime-suspended device back to full power.
+    fn runtime_resume(_data: &Self::Data) -> Result {
+        Ok(())
+    }
+
+    /// PCI driver runtime idle.
+    ///
+    /// Called when the device is considered idle; implementers may request a runtime suspend
+    /// from here. The default is to let the core decide.
+    fn runtime_idle(_data: &Self::Data) -> Result {
+        Ok(())
+    }
 }
 
 /// A PCI device.
@@ -272,6 +654,42 @@ impl Device {
         Resource::new(pdev.resource[index].start, pdev.resource[index].end)
     }
 
+    /// Returns the vendor ID of this device.
+    pub fn vendor_id(&self) -> VendorId {
+        let pdev = unsafe { &*self.ptr };
+        VendorId(pdev.vendor as u32)
+    }
+
+    /// Returns the device ID of this device.
+    pub fn device_id(&self) -> u32 {
+        let pdev = unsafe { &*self.ptr };
+        pdev.device as u32
+    }
+
+    /// Returns the subsystem vendor ID of this device.
+    pub fn subsystem_vendor(&self) -> VendorId {
+        let pdev = unsafe { &*self.ptr };
+        VendorId(pdev.subsystem_vendor as u32)
+    }
+
+    /// Returns the subsystem device ID of this device.
+    pub fn subsystem_device(&self) -> u32 {
+        let pdev = unsafe { &*self.ptr };
+        pdev.subsystem_device as u32
+    }
+
+    /// Returns the revision ID of this device.
+    pub fn revision(&self) -> u8 {
+        let pdev = unsafe { &*self.ptr };
+        pdev.revision
+    }
+
+    /// Returns the decoded class of this device.
+    pub fn class(&self) -> Class {
+        let pdev = unsafe { &*self.ptr };
+        Class::from_code(pdev.class)
+    }
+
     pub fn irq(&self) -> Option<u32> {
         let pdev = unsafe { &*self.ptr };
 
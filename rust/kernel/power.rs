@@ -0,0 +1,24 @@
+// SPDX-License-Identifier: GPL-2.0
+
+//! Power management types shared by the bus abstractions.
+//!
+//! C header: [`include/linux/pm.h`](../../../../include/linux/pm.h)
+
+use crate::bindings;
+
+/// Wraps the kernel's `pm_message_t`, which describes the reason for a system power
+/// transition (see the `PM_EVENT_*` constants in `include/linux/pm.h`).
+#[derive(Clone, Copy)]
+pub struct PmMessage(bindings::pm_message_t);
+
+impl PmMessage {
+    /// Creates a new instance from the raw C type.
+    pub(crate) fn from_raw(msg: bindings::pm_message_t) -> Self {
+        Self(msg)
+    }
+
+    /// Returns the numeric `PM_EVENT_*` code carried by this message.
+    pub fn event(&self) -> i32 {
+        self.0.event
+    }
+}